@@ -5,8 +5,11 @@
 //     This crate defines the fundamental types used across all AURIA components
 //     including Tensor, Shard, Expert, License, HardwareProfile, and error types.
 //
+use base64::Engine;
+use ed25519_dalek::{Signer, Verifier};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use zeroize::Zeroize;
 
 macro_rules! impl_hex_serialize {
     ($ty:ty, $len:expr) => {
@@ -36,13 +39,37 @@ macro_rules! impl_hex_serialize {
     };
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Tensor {
     pub data: Vec<u8>,
     pub shape: Vec<u32>,
     pub dtype: TensorDType,
 }
 
+impl<'de> Deserialize<'de> for Tensor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct TensorFields {
+            data: Vec<u8>,
+            shape: Vec<u32>,
+            dtype: TensorDType,
+        }
+        let fields = TensorFields::deserialize(deserializer)?;
+        let tensor = Tensor {
+            data: fields.data,
+            shape: fields.shape,
+            dtype: fields.dtype,
+        };
+        tensor
+            .validate_data_len()
+            .map_err(|e| serde::de::Error::custom(e.to_string()))?;
+        Ok(tensor)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[repr(u8)]
 pub enum TensorDType {
@@ -52,6 +79,121 @@ pub enum TensorDType {
     INT4,
 }
 
+impl TensorDType {
+    pub fn bits_per_element(&self) -> u32 {
+        match self {
+            TensorDType::FP16 => 16,
+            TensorDType::FP8 => 8,
+            TensorDType::INT8 => 8,
+            TensorDType::INT4 => 4,
+        }
+    }
+}
+
+/// Tagged wire representation of tensor bytes, so callers can pick a
+/// transport-friendly encoding (`base64`, `base58`) or skip encoding
+/// entirely for local use (`raw`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TensorEncoding {
+    Raw,
+    Base64,
+    Base58,
+}
+
+#[derive(Debug, Clone)]
+pub struct TensorData {
+    pub encoding: TensorEncoding,
+    pub data: Vec<u8>,
+}
+
+impl Serialize for TensorData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.encoding {
+            // Raw stays as an actual byte array: no point encoding to text
+            // for the one mode that exists to skip encoding overhead.
+            TensorEncoding::Raw => {
+                #[derive(Serialize)]
+                struct Repr<'a> {
+                    encoding: TensorEncoding,
+                    data: &'a [u8],
+                }
+                Repr {
+                    encoding: self.encoding,
+                    data: &self.data,
+                }
+                .serialize(serializer)
+            }
+            TensorEncoding::Base64 | TensorEncoding::Base58 => {
+                #[derive(Serialize)]
+                struct Repr {
+                    encoding: TensorEncoding,
+                    data: String,
+                }
+                let data = match self.encoding {
+                    TensorEncoding::Base64 => {
+                        base64::engine::general_purpose::STANDARD.encode(&self.data)
+                    }
+                    TensorEncoding::Base58 => bs58::encode(&self.data).into_string(),
+                    TensorEncoding::Raw => unreachable!(),
+                };
+                Repr {
+                    encoding: self.encoding,
+                    data,
+                }
+                .serialize(serializer)
+            }
+        }
+    }
+}
+
+/// The `data` field is a byte array for `raw` and a string for the text
+/// encodings, so deserialization accepts either shape and then matches it
+/// against the declared `encoding`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TensorDataField {
+    Bytes(Vec<u8>),
+    Text(String),
+}
+
+impl<'de> Deserialize<'de> for TensorData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Repr {
+            encoding: TensorEncoding,
+            data: TensorDataField,
+        }
+        let repr = Repr::deserialize(deserializer)?;
+        let data = match (repr.encoding, repr.data) {
+            (TensorEncoding::Raw, TensorDataField::Bytes(bytes)) => bytes,
+            (TensorEncoding::Base64, TensorDataField::Text(s)) => {
+                base64::engine::general_purpose::STANDARD
+                    .decode(&s)
+                    .map_err(|e| serde::de::Error::custom(e))?
+            }
+            (TensorEncoding::Base58, TensorDataField::Text(s)) => bs58::decode(&s)
+                .into_vec()
+                .map_err(|e| serde::de::Error::custom(e))?,
+            (encoding, _) => {
+                return Err(serde::de::Error::custom(format!(
+                    "data shape does not match encoding {encoding:?}"
+                )));
+            }
+        };
+        Ok(TensorData {
+            encoding: repr.encoding,
+            data,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Shard {
     pub shard_id: ShardId,
@@ -102,6 +244,24 @@ impl_hex_serialize!(Hash, 32);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Hash(pub [u8; 32]);
 
+/// Private key material. Deliberately does not implement `Serialize` or
+/// derive `Debug` — both would defeat the purpose of zeroizing it on drop
+/// by letting it leak out through logs or a persisted wire format.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SecretKey(pub [u8; 32]);
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct RuntimeVersion {
     pub major: u16,
@@ -119,6 +279,24 @@ impl RuntimeVersion {
     }
 }
 
+/// Capabilities a node advertises to a peer during handshake, signed as a
+/// unit so a peer can be held to what it claimed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeInfo {
+    pub pubkey: PublicKey,
+    pub runtime_version: RuntimeVersion,
+    pub tier: Tier,
+    pub hardware: HardwareProfile,
+}
+
+/// A signed `NodeInfo`, exchanged when two AURIA nodes pair before
+/// streaming experts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeHandshake {
+    pub info: NodeInfo,
+    pub signature: Signature,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Tier {
     Nano,
@@ -285,7 +463,12 @@ pub struct LicenseUsage {
     pub license_id: ShardId,
     pub node_pubkey: PublicKey,
     pub tokens_used: u64,
+    pub credits_spent: f64,
     pub requests_made: u64,
+    pub requests_made_today: u64,
+    pub day_started_at: u64,
+    pub concurrent_requests: u32,
+    pub rate_tokens_available: f64,
     pub last_updated: u64,
 }
 
@@ -333,6 +516,390 @@ pub struct ShardMetadata {
     pub version: u32,
 }
 
+/// An ed25519 keypair used to sign licenses, usage receipts, and other
+/// node-identity-bearing data.
+pub struct Keypair {
+    pub secret: SecretKey,
+    pub public: PublicKey,
+}
+
+impl Keypair {
+    pub fn from_secret(secret: SecretKey) -> Self {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&secret.0);
+        let public = PublicKey(signing_key.verifying_key().to_bytes());
+        Self { secret, public }
+    }
+
+    /// Generates a fresh node identity from an OS-provided CSPRNG.
+    pub fn generate() -> Self {
+        let signing_key = ed25519_dalek::SigningKey::generate(&mut rand::rngs::OsRng);
+        let secret = SecretKey(signing_key.to_bytes());
+        let public = PublicKey(signing_key.verifying_key().to_bytes());
+        Self { secret, public }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&self.secret.0);
+        Signature(signing_key.sign(message).to_bytes())
+    }
+}
+
+fn push_len_prefixed(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn verify_signature(signer: &PublicKey, message: &[u8], signature: &Signature) -> AuriaResult<()> {
+    let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&signer.0)
+        .map_err(|e| AuriaError::SecurityError(format!("invalid public key: {e}")))?;
+    let signature = ed25519_dalek::Signature::from_bytes(&signature.0);
+    verifying_key
+        .verify(message, &signature)
+        .map_err(|e| AuriaError::SecurityError(format!("signature verification failed: {e}")))
+}
+
+impl License {
+    /// Deterministic encoding of every fixed-size field except the
+    /// signature itself, bare-concatenated (no length prefixes needed
+    /// since none of these fields are variable-length):
+    /// `shard_id || node_pubkey || expiry_timestamp`.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(32 + 32 + 8);
+        buf.extend_from_slice(&self.shard_id.0);
+        buf.extend_from_slice(&self.node_pubkey.0);
+        buf.extend_from_slice(&self.expiry_timestamp.to_le_bytes());
+        buf
+    }
+
+    pub fn sign(&mut self, keypair: &Keypair) {
+        self.signature = keypair.sign(&self.canonical_bytes());
+    }
+
+    pub fn verify(&self) -> AuriaResult<()> {
+        verify_signature(&self.node_pubkey, &self.canonical_bytes(), &self.signature)
+    }
+}
+
+impl UsageReceipt {
+    /// Deterministic, length-prefixed encoding of every field except the
+    /// signature itself: `request_id || expert_ids || token_count || timestamp`.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(16 + 4 + self.expert_ids.len() * 32 + 4 + 8);
+        buf.extend_from_slice(&self.request_id.0);
+        let expert_ids: Vec<u8> = self.expert_ids.iter().flat_map(|id| id.0).collect();
+        push_len_prefixed(&mut buf, &expert_ids);
+        buf.extend_from_slice(&self.token_count.to_le_bytes());
+        buf.extend_from_slice(&self.timestamp.to_le_bytes());
+        buf
+    }
+
+    pub fn sign(&mut self, keypair: &Keypair) {
+        self.node_signature = keypair.sign(&self.canonical_bytes());
+    }
+
+    pub fn verify(&self, signer: &PublicKey) -> AuriaResult<()> {
+        verify_signature(signer, &self.canonical_bytes(), &self.node_signature)
+    }
+}
+
+impl Tensor {
+    pub fn element_count(&self) -> usize {
+        self.shape.iter().map(|&d| d as usize).product()
+    }
+
+    fn validate_data_len(&self) -> AuriaResult<()> {
+        let expected_bits = self.element_count() as u64 * self.dtype.bits_per_element() as u64;
+        let expected_bytes = expected_bits.div_ceil(8);
+        if self.data.len() as u64 != expected_bytes {
+            return Err(AuriaError::SerializationError(format!(
+                "tensor data length {} does not match expected {} bytes for shape {:?} and dtype {:?}",
+                self.data.len(),
+                expected_bytes,
+                self.shape,
+                self.dtype
+            )));
+        }
+        Ok(())
+    }
+
+    /// Produces a tagged wire representation of this tensor's bytes in the
+    /// given encoding, leaving `shape`/`dtype` to travel alongside it.
+    pub fn with_encoding(&self, encoding: TensorEncoding) -> TensorData {
+        TensorData {
+            encoding,
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl Shard {
+    /// Content address for this shard: `BLAKE3(dtype || shape_len || shape || data)`.
+    pub fn compute_id(&self) -> ShardId {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&[self.tensor.dtype as u8]);
+        hasher.update(&(self.tensor.shape.len() as u32).to_le_bytes());
+        for dim in &self.tensor.shape {
+            hasher.update(&dim.to_le_bytes());
+        }
+        hasher.update(&self.tensor.data);
+        ShardId(*hasher.finalize().as_bytes())
+    }
+
+    pub fn verify_id(&self) -> bool {
+        self.compute_id() == self.shard_id
+    }
+}
+
+fn merkle_leaf(shard_id: &ShardId) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[0x00]);
+    hasher.update(&shard_id.0);
+    *hasher.finalize().as_bytes()
+}
+
+fn merkle_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Every level of the tree, bottom (leaves) to top (root), each padded by
+/// duplicating its last node when its length is odd and greater than one.
+fn merkle_tree_levels(sorted_ids: &[ShardId]) -> Vec<Vec<[u8; 32]>> {
+    let mut level: Vec<[u8; 32]> = sorted_ids.iter().map(merkle_leaf).collect();
+    if level.is_empty() {
+        return Vec::new();
+    }
+
+    let mut levels = Vec::new();
+    loop {
+        if level.len() > 1 && level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+        levels.push(level.clone());
+        if level.len() <= 1 {
+            break;
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| merkle_parent(&pair[0], &pair[1]))
+            .collect();
+    }
+    levels
+}
+
+/// One step of a Merkle inclusion proof: the sibling hash and which side
+/// of the pair it sits on, so the verifier can rebuild the path
+/// positionally instead of guessing from hash order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProofStep {
+    pub sibling: Hash,
+    pub sibling_is_right: bool,
+}
+
+impl Expert {
+    /// Merkle root over this expert's sorted member `ShardId`s, with odd
+    /// levels completed by duplicating their last node.
+    pub fn merkle_root(&self) -> Hash {
+        let mut sorted_ids = self.shards.clone();
+        sorted_ids.sort_by_key(|id| id.0);
+        match merkle_tree_levels(&sorted_ids).last() {
+            Some(root_level) => Hash(root_level[0]),
+            None => Hash([0u8; 32]),
+        }
+    }
+
+    pub fn compute_expert_id(&self) -> ExpertId {
+        ExpertId(self.merkle_root().0)
+    }
+
+    pub fn verify_id(&self) -> bool {
+        self.compute_expert_id() == self.expert_id
+    }
+
+    /// Builds the Merkle inclusion proof for `shard_id`, i.e. the sibling
+    /// at each level from its leaf up to (but excluding) the root.
+    /// Returns `None` if `shard_id` is not a member.
+    pub fn merkle_proof(&self, shard_id: ShardId) -> Option<Vec<MerkleProofStep>> {
+        let mut sorted_ids = self.shards.clone();
+        sorted_ids.sort_by_key(|id| id.0);
+        let mut index = sorted_ids.iter().position(|id| *id == shard_id)?;
+
+        let levels = merkle_tree_levels(&sorted_ids);
+        let mut proof = Vec::with_capacity(levels.len().saturating_sub(1));
+        for level in &levels[..levels.len() - 1] {
+            let sibling_index = index ^ 1;
+            proof.push(MerkleProofStep {
+                sibling: Hash(level[sibling_index]),
+                sibling_is_right: sibling_index > index,
+            });
+            index /= 2;
+        }
+        Some(proof)
+    }
+
+    /// Verifies that `shard_id` is a member of this expert given a Merkle
+    /// inclusion proof produced by [`Expert::merkle_proof`].
+    pub fn verify_membership(&self, shard_id: ShardId, proof: &[MerkleProofStep]) -> bool {
+        let mut current = merkle_leaf(&shard_id);
+        for step in proof {
+            current = if step.sibling_is_right {
+                merkle_parent(&current, &step.sibling.0)
+            } else {
+                merkle_parent(&step.sibling.0, &current)
+            };
+        }
+        current == self.merkle_root().0
+    }
+}
+
+impl RateLimit {
+    /// Token bucket check: the bucket holds up to `burst_size` tokens,
+    /// refilling at `requests_per_second` tokens per second since
+    /// `usage.last_updated`. The refilled level is persisted in
+    /// `usage.rate_tokens_available` and one token is decremented on
+    /// admission, so up to `burst_size` requests can burst back-to-back.
+    pub fn check_and_consume(&self, usage: &mut LicenseUsage, now: u64) -> AuriaResult<()> {
+        let elapsed_secs = now.saturating_sub(usage.last_updated) as f64;
+        let refilled = (usage.rate_tokens_available + elapsed_secs * self.requests_per_second as f64)
+            .min(self.burst_size as f64);
+        if refilled < 1.0 {
+            return Err(AuriaError::RateLimitExceeded(format!(
+                "only {refilled:.2} of {} burst tokens available",
+                self.burst_size
+            )));
+        }
+        usage.rate_tokens_available = refilled - 1.0;
+        usage.last_updated = now;
+        Ok(())
+    }
+}
+
+/// Decides whether a request against a license is allowed, and accounts
+/// for it in the caller-owned `LicenseUsage` once admitted.
+pub struct LicenseEnforcer;
+
+impl LicenseEnforcer {
+    pub fn check_request(
+        terms: &LicenseTerms,
+        usage: &mut LicenseUsage,
+        now: u64,
+        tokens_requested: u64,
+    ) -> AuriaResult<()> {
+        let mut credit_cost = 0.0_f64;
+
+        match &terms.license_type {
+            LicenseType::Subscription {
+                max_requests_per_day,
+                ..
+            } => {
+                const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+                if now.saturating_sub(usage.day_started_at) >= SECONDS_PER_DAY {
+                    usage.day_started_at = now;
+                    usage.requests_made_today = 0;
+                }
+                if usage.requests_made_today >= *max_requests_per_day {
+                    return Err(AuriaError::RateLimitExceeded(format!(
+                        "subscription daily limit of {max_requests_per_day} requests reached"
+                    )));
+                }
+            }
+            LicenseType::PayPerUse {
+                credits,
+                cost_per_token,
+            } => {
+                let cost = tokens_requested as f64 * cost_per_token;
+                let remaining = *credits as f64 - usage.credits_spent;
+                if cost > remaining {
+                    return Err(AuriaError::RateLimitExceeded(format!(
+                        "insufficient credits: {remaining:.2} remaining, {cost:.2} required"
+                    )));
+                }
+                credit_cost = cost;
+            }
+            LicenseType::Enterprise {
+                unlimited,
+                max_concurrent_requests,
+            } => {
+                if !unlimited && usage.concurrent_requests >= *max_concurrent_requests {
+                    return Err(AuriaError::RateLimitExceeded(format!(
+                        "concurrency limit of {max_concurrent_requests} requests reached"
+                    )));
+                }
+            }
+            LicenseType::Community { .. } => {}
+        }
+
+        // Only consume a rate-limit token once the license-type gate has
+        // already admitted the request, so a denied request never burns a
+        // burst token.
+        if let Some(rate_limit) = &terms.rate_limit {
+            rate_limit.check_and_consume(usage, now)?;
+        }
+
+        usage.tokens_used += tokens_requested;
+        usage.credits_spent += credit_cost;
+        usage.requests_made += 1;
+        usage.requests_made_today += 1;
+        usage.concurrent_requests += 1;
+        usage.last_updated = now;
+        Ok(())
+    }
+
+    /// Releases the concurrency slot reserved by `check_request` once the
+    /// request has finished.
+    pub fn finish_request(usage: &mut LicenseUsage) {
+        usage.concurrent_requests = usage.concurrent_requests.saturating_sub(1);
+    }
+}
+
+impl NodeInfo {
+    /// Deterministic, length-prefixed encoding of every field: fixed-size
+    /// fields are laid out directly and `hardware` (variable-sized) is
+    /// length-prefixed.
+    pub fn canonical_bytes(&self) -> AuriaResult<Vec<u8>> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&self.pubkey.0);
+        buf.extend_from_slice(&self.runtime_version.major.to_le_bytes());
+        buf.extend_from_slice(&self.runtime_version.minor.to_le_bytes());
+        buf.extend_from_slice(&self.runtime_version.patch.to_le_bytes());
+        buf.push(self.tier as u8);
+        let hardware_bytes = serde_json::to_vec(&self.hardware)
+            .map_err(|e| AuriaError::SerializationError(e.to_string()))?;
+        push_len_prefixed(&mut buf, &hardware_bytes);
+        Ok(buf)
+    }
+}
+
+impl NodeHandshake {
+    pub fn new(keypair: &Keypair, info: NodeInfo) -> AuriaResult<Self> {
+        let signature = keypair.sign(&info.canonical_bytes()?);
+        Ok(Self { info, signature })
+    }
+
+    /// Checks the peer's signature over its own `NodeInfo` and confirms
+    /// this node's `RuntimeVersion` is compatible (same major version).
+    pub fn verify(&self) -> AuriaResult<()> {
+        verify_signature(
+            &self.info.pubkey,
+            &self.info.canonical_bytes()?,
+            &self.signature,
+        )?;
+
+        let local = RuntimeVersion::current();
+        if self.info.runtime_version.major != local.major {
+            return Err(AuriaError::SecurityError(format!(
+                "incompatible runtime version: peer major {} != local major {}",
+                self.info.runtime_version.major, local.major
+            )));
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Display for Tier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -378,6 +945,9 @@ pub enum AuriaError {
 
     #[error("Cluster error: {0}")]
     ClusterError(String),
+
+    #[error("Rate limit exceeded: {0}")]
+    RateLimitExceeded(String),
 }
 
 pub type AuriaResult<T> = std::result::Result<T, AuriaError>;